@@ -0,0 +1,54 @@
+//! Base58 cipher implementation.
+
+use anyhow::anyhow;
+
+use super::{base58_decode, base58_encode, xor_with_key, Cipher};
+
+/// Cipher that keys the secret with the shared key and renders it as a base58 string.
+#[derive(Default)]
+pub struct Base58Cipher;
+
+impl Cipher for Base58Cipher {
+    fn transform(&self, secret: &str, key: &str) -> String {
+        let keyed = xor_with_key(secret.as_bytes(), key.as_bytes());
+        base58_encode(&keyed)
+    }
+
+    fn recover(&self, ciphered: &str, key: &str) -> anyhow::Result<String> {
+        let keyed = base58_decode(ciphered)?;
+        let secret = xor_with_key(&keyed, key.as_bytes());
+        String::from_utf8(secret).map_err(|e| anyhow!("decoded secret is not valid UTF-8: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_round_trips_through_recover() {
+        let cipher = Base58Cipher;
+        let ciphered = cipher.transform("attack at dawn", "shared-key");
+        let plaintext = cipher
+            .recover(&ciphered, "shared-key")
+            .expect("recover should succeed with the right key");
+        assert_eq!(plaintext, "attack at dawn");
+    }
+
+    #[test]
+    fn recover_with_wrong_key_yields_different_plaintext() {
+        let cipher = Base58Cipher;
+        let ciphered = cipher.transform("attack at dawn", "shared-key");
+        let plaintext = cipher
+            .recover(&ciphered, "wrong-key")
+            .expect("base58 has no checksum, so decoding still succeeds");
+        assert_ne!(plaintext, "attack at dawn");
+    }
+
+    #[test]
+    fn recover_fails_on_invalid_character() {
+        let cipher = Base58Cipher;
+        let result = cipher.recover("not-valid-base58!", "shared-key");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! Blech32 cipher implementation: like bech32, but with a longer checksum suited to longer
+//! payloads.
+
+use anyhow::anyhow;
+
+use super::{convert_bits, hrp_expand, parse, render, xor_with_key, Cipher};
+
+const GENERATOR: [u64; 5] = [
+    0x7d52fba40bd886,
+    0x5e8dbf1a03950c,
+    0x1c3a3c74072a18,
+    0x385d72fa0e5139,
+    0x7093e5a608865b,
+];
+
+fn polymod(values: &[u8]) -> u64 {
+    let mut chk: u64 = 1;
+    for &v in values {
+        let top = chk >> 55;
+        chk = (chk & 0x7fffffffffffff) << 5 ^ (v as u64);
+        for (i, gen) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 != 0 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+fn create_checksum(hrp: &str, data: &[u8]) -> Vec<u8> {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0; 12]);
+    let checksum = polymod(&values) ^ 1;
+    (0..12)
+        .map(|i| ((checksum >> (5 * (11 - i))) & 31) as u8)
+        .collect()
+}
+
+fn verify_checksum(hrp: &str, data: &[u8], checksum: &[u8]) -> bool {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(checksum);
+    polymod(&values) == 1
+}
+
+/// Cipher that keys the secret with the shared key and renders it as blech32 (a bech32 variant
+/// with a 12-symbol checksum, suited to longer payloads), using the shared key as the
+/// human-readable prefix (HRP).
+#[derive(Default)]
+pub struct Blech32Cipher;
+
+impl Cipher for Blech32Cipher {
+    fn transform(&self, secret: &str, key: &str) -> String {
+        let keyed = xor_with_key(secret.as_bytes(), key.as_bytes());
+        let data = convert_bits(&keyed, 8, 5, true).expect("8-to-5 bit conversion cannot fail");
+        let checksum = create_checksum(key, &data);
+        render(key, &data, &checksum)
+    }
+
+    fn recover(&self, ciphered: &str, key: &str) -> anyhow::Result<String> {
+        let (hrp, data, checksum) = parse(ciphered, 12)?;
+        if hrp != key {
+            return Err(anyhow!("HRP '{hrp}' does not match the shared key"));
+        }
+        if !verify_checksum(&hrp, &data, &checksum) {
+            return Err(anyhow!("blech32 checksum mismatch"));
+        }
+        let keyed = convert_bits(&data, 5, 8, false)
+            .ok_or_else(|| anyhow!("invalid 5-to-8 bit conversion"))?;
+        let secret = xor_with_key(&keyed, key.as_bytes());
+        String::from_utf8(secret).map_err(|e| anyhow!("decoded secret is not valid UTF-8: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_round_trips_through_recover() {
+        let cipher = Blech32Cipher;
+        let ciphered = cipher.transform("the world shall tremble at dawn", "villain");
+        let plaintext = cipher
+            .recover(&ciphered, "villain")
+            .expect("recover should succeed with the right key and HRP");
+        assert_eq!(plaintext, "the world shall tremble at dawn");
+    }
+
+    #[test]
+    fn recover_fails_with_wrong_key() {
+        let cipher = Blech32Cipher;
+        let ciphered = cipher.transform("the world shall tremble at dawn", "villain");
+        let result = cipher.recover(&ciphered, "sidekick");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn recover_fails_on_tampered_checksum() {
+        let cipher = Blech32Cipher;
+        let mut ciphered = cipher.transform("the world shall tremble at dawn", "villain");
+        let last = ciphered.pop().expect("ciphertext is non-empty");
+        let replacement = if last == 'q' { 'p' } else { 'q' };
+        ciphered.push(replacement);
+        let result = cipher.recover(&ciphered, "villain");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,178 @@
+#![allow(dead_code)]
+//! Module for ciphers and all the related functionality
+
+mod base58;
+mod bech32;
+mod blech32;
+
+use anyhow::anyhow;
+#[cfg(test)]
+use mockall::automock;
+
+pub use base58::Base58Cipher;
+pub use bech32::Bech32Cipher;
+pub use blech32::Blech32Cipher;
+
+/// Type that represents a cipher.
+#[cfg_attr(test, automock)]
+pub trait Cipher {
+    fn transform(&self, secret: &str, key: &str) -> String;
+    fn recover(&self, ciphered: &str, key: &str) -> anyhow::Result<String>;
+}
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Derive a simple repeating-key keystream of the requested length.
+///
+/// This is not cryptographically secure; it merely keys the secret with the shared key so
+/// that the resulting ciphertext depends on both.
+fn key_stream(key: &[u8], len: usize) -> Vec<u8> {
+    if key.is_empty() {
+        return vec![0; len];
+    }
+    (0..len).map(|i| key[i % key.len()]).collect()
+}
+
+/// XOR `data` with a keystream derived from `key`.
+fn xor_with_key(data: &[u8], key: &[u8]) -> Vec<u8> {
+    data.iter()
+        .zip(key_stream(key, data.len()))
+        .map(|(byte, ks)| byte ^ ks)
+        .collect()
+}
+
+/// Base58-encode `data` using the Bitcoin alphabet, preserving leading zero bytes as `'1'`.
+fn base58_encode(data: &[u8]) -> String {
+    let mut digits: Vec<u8> = vec![0];
+    for &byte in data {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let leading_zeros = data.iter().take_while(|&&b| b == 0).count();
+    let mut result: String = "1".repeat(leading_zeros);
+    result.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&d| BASE58_ALPHABET[d as usize] as char),
+    );
+    result
+}
+
+/// Decode a base58 string produced by [`base58_encode`] back into bytes.
+fn base58_decode(encoded: &str) -> anyhow::Result<Vec<u8>> {
+    if encoded.chars().all(|c| c == '1') {
+        return Ok(vec![0; encoded.len().saturating_sub(1)]);
+    }
+    let mut bytes: Vec<u8> = vec![0];
+    for c in encoded.chars() {
+        let digit = BASE58_ALPHABET
+            .iter()
+            .position(|&b| b as char == c)
+            .ok_or_else(|| anyhow!("invalid base58 character: '{c}'"))? as u32;
+        let mut carry = digit;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    bytes.reverse();
+    let leading_ones = encoded.chars().take_while(|&c| c == '1').count();
+    let leading_zeros_already = bytes.iter().take_while(|&&b| b == 0).count();
+    if leading_ones > leading_zeros_already {
+        let mut padded = vec![0u8; leading_ones - leading_zeros_already];
+        padded.extend_from_slice(&bytes);
+        bytes = padded;
+    }
+    Ok(bytes)
+}
+
+/// Re-group `data` from `from_bits`-wide values into `to_bits`-wide values, as used to turn
+/// bytes into the 5-bit groups bech32/blech32 encode.
+fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let maxv: u32 = (1 << to_bits) - 1;
+    let max_acc: u32 = (1 << (from_bits + to_bits - 1)) - 1;
+    let mut ret = Vec::new();
+    for &value in data {
+        let value = value as u32;
+        if (value >> from_bits) != 0 {
+            return None;
+        }
+        acc = ((acc << from_bits) | value) & max_acc;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            ret.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to_bits - bits)) & maxv) as u8);
+        }
+    } else if bits >= from_bits || ((acc << (to_bits - bits)) & maxv) != 0 {
+        return None;
+    }
+    Some(ret)
+}
+
+/// Expand the human-readable part the way bech32/blech32 fold it into the checksum.
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut expanded: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    expanded.push(0);
+    expanded.extend(hrp.bytes().map(|b| b & 31));
+    expanded
+}
+
+/// Split a bech32/blech32-style string into its HRP, payload and `checksum_len`-long checksum
+/// (each as 5-bit groups), validating the charset along the way.
+fn parse(encoded: &str, checksum_len: usize) -> anyhow::Result<(String, Vec<u8>, Vec<u8>)> {
+    let sep = encoded
+        .rfind('1')
+        .ok_or_else(|| anyhow!("missing '1' separator in '{encoded}'"))?;
+    let hrp = encoded[..sep].to_string();
+    let payload = &encoded[sep + 1..];
+    if payload.len() < checksum_len {
+        return Err(anyhow!("payload too short to contain a checksum"));
+    }
+    let values = payload
+        .bytes()
+        .map(|b| {
+            BECH32_CHARSET
+                .iter()
+                .position(|&c| c == b)
+                .map(|p| p as u8)
+                .ok_or_else(|| anyhow!("invalid character '{}' in payload", b as char))
+        })
+        .collect::<anyhow::Result<Vec<u8>>>()?;
+    let (data, checksum) = values.split_at(values.len() - checksum_len);
+    Ok((hrp, data.to_vec(), checksum.to_vec()))
+}
+
+/// Render `hrp`, `data` (5-bit groups) and `checksum` (5-bit groups) as `hrp1<data><checksum>`.
+fn render(hrp: &str, data: &[u8], checksum: &[u8]) -> String {
+    let mut out = String::with_capacity(hrp.len() + 1 + data.len() + checksum.len());
+    out.push_str(hrp);
+    out.push('1');
+    out.extend(
+        data.iter()
+            .chain(checksum.iter())
+            .map(|&v| BECH32_CHARSET[v as usize] as char),
+    );
+    out
+}
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+//! Module for time sources and all the related functionality
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+
+/// Trait that abstracts a source of time, so that waiting and measuring elapsed time can be
+/// swapped out for a deterministic implementation in tests.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TimeSource: Send + Sync {
+    /// Suspend execution for the given duration.
+    async fn sleep(&self, d: Duration);
+    /// Time elapsed since this time source was created.
+    fn elapsed(&self) -> Duration;
+}
+
+/// Time source backed by the tokio runtime.
+pub struct RealClock {
+    start: Instant,
+}
+
+impl RealClock {
+    pub fn new() -> Self {
+        RealClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TimeSource for RealClock {
+    async fn sleep(&self, d: Duration) {
+        tokio::time::sleep(d).await;
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
@@ -1,4 +1,5 @@
 mod cipher;
+mod clock;
 mod gadget;
 mod henchman;
 mod sidekick;
@@ -6,7 +7,8 @@ mod supervillain;
 #[cfg(test)]
 mod test_common;
 
-pub use cipher::Cipher;
+pub use cipher::{Base58Cipher, Bech32Cipher, Blech32Cipher, Cipher};
+pub use clock::TimeSource;
 pub use gadget::Gadget;
 pub use henchman::Henchman;
 pub use sidekick::Sidekick;
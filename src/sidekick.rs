@@ -4,7 +4,7 @@
 #[cfg(test)]
 use mockall::mock;
 
-use crate::Gadget;
+use crate::{Cipher, Gadget};
 
 /// Type that represents a sidekick.
 pub struct Sidekick<'a> {
@@ -27,6 +27,15 @@ impl<'a> Sidekick<'a> {
     }
 
     pub fn tell(&self, _ciphered_msg: String) {}
+
+    pub fn decipher<C: Cipher>(
+        &self,
+        ciphered: String,
+        cipher: &C,
+        key: &str,
+    ) -> anyhow::Result<String> {
+        cipher.recover(&ciphered, key)
+    }
 }
 
 #[cfg(test)]
@@ -35,5 +44,6 @@ mock! {
         pub fn agree(&self) -> bool;
         pub fn get_weak_targets(&self, _gadget: &'a dyn Gadget) -> Vec<String>;
         pub fn tell(&self, _ciphered_msg: String);
+        pub fn decipher(&self, ciphered: String, cipher: &'a dyn Cipher, key: &str) -> anyhow::Result<String>;
     }
 }
@@ -2,23 +2,87 @@
 //! Module for supervillains and their related stuff
 use std::time::Duration;
 
-use anyhow::anyhow;
 #[cfg(test)]
 use mockall::{automock, predicate::eq};
 #[cfg(test)]
 use mockall_double::double;
 
+use crate::clock::RealClock;
 #[cfg_attr(test, double)]
 use crate::sidekick::Sidekick;
-use crate::{Cipher, Gadget, Henchman};
+use crate::{Cipher, Gadget, Henchman, TimeSource};
 
 /// Type that represents supervillains.
 #[derive(Default)]
 pub struct Supervillain<'a> {
     pub first_name: String,
+    pub middle_name: Option<String>,
     pub last_name: String,
     pub sidekick: Option<Sidekick<'a>>,
     pub shared_key: String,
+    pub clock: Option<Box<dyn TimeSource + 'a>>,
+}
+
+/// Errors that can occur while parsing a full name into its components.
+#[derive(Debug, PartialEq, Eq)]
+pub enum NameError {
+    /// The name was empty or contained only whitespace.
+    Empty,
+    /// The name had a single component; a last name is required.
+    MissingLastName { name: String },
+    /// The name had more components than fit in first, middle and last name.
+    TooManyComponents { found: usize, name: String },
+}
+
+impl std::fmt::Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "name must not be empty"),
+            NameError::MissingLastName { name } => {
+                write!(f, "name '{name}' is missing a last name")
+            }
+            NameError::TooManyComponents { found, name } => {
+                write!(
+                    f,
+                    "name '{name}' has {found} components; at most 3 are supported"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
+
+/// The parsed components of a full name.
+struct NameComponents {
+    first_name: String,
+    middle_name: Option<String>,
+    last_name: String,
+}
+
+/// Split `name` on whitespace into first, optional middle, and last name components.
+fn parse_name(name: &str) -> Result<NameComponents, NameError> {
+    let components: Vec<&str> = name.split_whitespace().collect();
+    match components.as_slice() {
+        [] => Err(NameError::Empty),
+        [_only] => Err(NameError::MissingLastName {
+            name: name.to_string(),
+        }),
+        [first, last] => Ok(NameComponents {
+            first_name: first.to_string(),
+            middle_name: None,
+            last_name: last.to_string(),
+        }),
+        [first, middle, last] => Ok(NameComponents {
+            first_name: first.to_string(),
+            middle_name: Some(middle.to_string()),
+            last_name: last.to_string(),
+        }),
+        _ => Err(NameError::TooManyComponents {
+            found: components.len(),
+            name: name.to_string(),
+        }),
+    }
 }
 
 #[cfg_attr(test, automock)]
@@ -29,7 +93,8 @@ pub trait Megaweapon {
 impl Supervillain<'_> {
     /// Return the value of the full name as a single string.
     ///
-    /// Full name is produced concatenating first name, a single space, and the last name.
+    /// Full name is produced concatenating first name, optional middle name, and last name,
+    /// each separated by a single space.
     ///
     /// # Examples
     /// ```
@@ -37,26 +102,35 @@ impl Supervillain<'_> {
     /// let lex = Supervillain {
     ///     first_name: "Lex".to_string(),
     ///     last_name: "Luthor".to_string(),
+    ///     ..Default::default()
     /// };
     /// assert_eq!(lex.full_name(), "Lex Luthor");
     /// ```
     pub fn full_name(&self) -> String {
-        format!("{} {}", self.first_name, self.last_name)
-    }
-    pub fn set_full_name(&mut self, name: &str) {
-        let components = name.split(" ").collect::<Vec<_>>();
-        println!("Received {} components.", components.len());
-        if components.len() != 2 {
-            panic!("Name must have first and last name");
+        match &self.middle_name {
+            Some(middle_name) => {
+                format!("{} {} {}", self.first_name, middle_name, self.last_name)
+            }
+            None => format!("{} {}", self.first_name, self.last_name),
         }
-        self.first_name = components[0].to_string();
-        self.last_name = components[1].to_string();
+    }
+    /// Parse `name` into first, optional middle, and last name components.
+    ///
+    /// Names with more than three components are rejected with [`NameError::TooManyComponents`].
+    pub fn set_full_name(&mut self, name: &str) -> Result<(), NameError> {
+        let parsed = parse_name(name)?;
+        self.first_name = parsed.first_name;
+        self.middle_name = parsed.middle_name;
+        self.last_name = parsed.last_name;
+        Ok(())
     }
     pub fn attack(&self, weapon: &impl Megaweapon) {
         weapon.shoot();
     }
     pub async fn come_up_with_plan(&self) -> String {
-        tokio::time::sleep(Duration::from_millis(100)).await;
+        let default_clock = RealClock::new();
+        let clock: &dyn TimeSource = self.clock.as_deref().unwrap_or(&default_clock);
+        clock.sleep(Duration::from_millis(100)).await;
         String::from("Take over the world!")
     }
     pub fn conspire(&mut self) {
@@ -85,28 +159,36 @@ impl Supervillain<'_> {
         henchman.fight_enemies();
     }
 
-    pub fn tell_plans<C: Cipher>(&self, secret: &str, cipher: &C) {
+    /// Cipher `secret` and hand it to the sidekick, if there is one.
+    ///
+    /// Returns the round-tripped plaintext recovered by deciphering the message back, so
+    /// callers can verify that what the sidekick received matches what was sent.
+    pub fn tell_plans<C: Cipher>(
+        &self,
+        secret: &str,
+        cipher: &C,
+    ) -> Option<anyhow::Result<String>> {
         if let Some(ref sidekick) = self.sidekick {
             let ciphered_msg = cipher.transform(secret, &self.shared_key);
-            sidekick.tell(ciphered_msg);
+            sidekick.tell(ciphered_msg.clone());
+            Some(sidekick.decipher(ciphered_msg, cipher, &self.shared_key))
+        } else {
+            None
         }
     }
 }
 
 impl TryFrom<&str> for Supervillain<'_> {
-    type Error = anyhow::Error;
+    type Error = NameError;
     fn try_from(name: &str) -> Result<Self, Self::Error> {
-        let components = name.split(" ").collect::<Vec<_>>();
-        if components.len() < 2 {
-            Err(anyhow!("Too few arguments"))
-        } else {
-            Ok(Supervillain {
-                first_name: components[0].to_string(),
-                last_name: components[1].to_string(),
-                sidekick: None,
-                ..Default::default()
-            })
-        }
+        let parsed = parse_name(name)?;
+        Ok(Supervillain {
+            first_name: parsed.first_name,
+            middle_name: parsed.middle_name,
+            last_name: parsed.last_name,
+            sidekick: None,
+            ..Default::default()
+        })
     }
 }
 
@@ -117,7 +199,8 @@ mod tests {
 
     use super::*;
 
-    use crate::cipher::MockCipher;
+    use crate::cipher::{Base58Cipher, Bech32Cipher};
+    use crate::clock::MockTimeSource;
     use crate::gadget::MockGadget;
     use crate::henchman::MockHenchman;
     use crate::test_common;
@@ -132,27 +215,63 @@ mod tests {
     }
     #[test_context(Context)]
     #[test]
-    fn set_full_name_sets_first_and_last_name(ctx: &mut Context) {
+    fn set_full_name_sets_first_and_last_name(ctx: &mut Context) -> Result<(), NameError> {
         // Act
-        ctx.sut.set_full_name(test_common::SECONDARY_FULL_NAME);
+        ctx.sut.set_full_name(test_common::SECONDARY_FULL_NAME)?;
         // Assert
         assert_eq!(ctx.sut.first_name, test_common::SECONDARY_FIRST_NAME);
+        assert_eq!(ctx.sut.middle_name, None);
         assert_eq!(ctx.sut.last_name, test_common::SECONDARY_LAST_NAME);
+        Ok(())
     }
     #[test_context(Context)]
-    // #[ignore]
     #[test]
-    #[should_panic(expected = "Name must have first and last name")]
-    fn set_full_name_panics_with_empty_name(ctx: &mut Context) {
-        // Arrange
-
+    fn set_full_name_folds_middle_component(ctx: &mut Context) -> Result<(), NameError> {
+        // Act
+        ctx.sut.set_full_name("Victor Von Doom")?;
+        // Assert
+        assert_eq!(ctx.sut.first_name, "Victor");
+        assert_eq!(ctx.sut.middle_name, Some("Von".to_string()));
+        assert_eq!(ctx.sut.last_name, "Doom");
+        Ok(())
+    }
+    #[test_context(Context)]
+    #[test]
+    fn set_full_name_errors_with_empty_name(ctx: &mut Context) {
+        // Act
+        let result = ctx.sut.set_full_name("");
+        // Assert
+        assert_eq!(result, Err(NameError::Empty));
+    }
+    #[test_context(Context)]
+    #[test]
+    fn set_full_name_errors_with_a_single_component(ctx: &mut Context) {
         // Act
-        ctx.sut.set_full_name("");
+        let result = ctx.sut.set_full_name("Doom");
         // Assert
+        assert_eq!(
+            result,
+            Err(NameError::MissingLastName {
+                name: "Doom".to_string()
+            })
+        );
     }
+    #[test_context(Context)]
     #[test]
-    fn from_str_slice_produces_supervillain_with_first_and_last_name() -> Result<(), anyhow::Error>
-    {
+    fn set_full_name_errors_with_too_many_components(ctx: &mut Context) {
+        // Act
+        let result = ctx.sut.set_full_name("Victor Von Doom The First");
+        // Assert
+        assert_eq!(
+            result,
+            Err(NameError::TooManyComponents {
+                found: 5,
+                name: "Victor Von Doom The First".to_string()
+            })
+        );
+    }
+    #[test]
+    fn from_str_slice_produces_supervillain_with_first_and_last_name() -> Result<(), NameError> {
         // Act
         let sut = Supervillain::try_from(test_common::SECONDARY_FULL_NAME)?;
         // Assert
@@ -161,14 +280,22 @@ mod tests {
         Ok(())
     }
     #[test]
+    fn from_str_slice_produces_supervillain_with_middle_name() -> Result<(), NameError> {
+        // Act
+        let sut = Supervillain::try_from("Victor Von Doom")?;
+        // Assert
+        assert_eq!(sut.first_name, "Victor");
+        assert_eq!(sut.middle_name, Some("Von".to_string()));
+        assert_eq!(sut.last_name, "Doom");
+        Ok(())
+    }
+    #[test]
     // #[ignore]
     fn from_str_slice_produces_error_with_less_than_two_substrings() {
         // Act
         let result = Supervillain::try_from("");
         // Assert
-        let Err(_) = result else {
-            panic!("Unexpected value returned by try_from");
-        };
+        assert!(matches!(result, Err(NameError::Empty)));
     }
     #[test_context(Context)]
     #[test]
@@ -185,6 +312,21 @@ mod tests {
     async fn plan_is_sadly_expected(ctx: &mut AsyncContext<'static>) {
         assert_eq!(ctx.sut.come_up_with_plan().await, "Take over the world!");
     }
+    #[test_context(AsyncContext)]
+    #[tokio::test]
+    async fn plan_sleeps_for_the_requested_duration_using_injected_clock(
+        ctx: &mut AsyncContext<'static>,
+    ) {
+        let mut mock_clock = MockTimeSource::new();
+        mock_clock
+            .expect_sleep()
+            .with(eq(Duration::from_millis(100)))
+            .once()
+            .returning(|_| ());
+        ctx.sut.clock = Some(Box::new(mock_clock));
+
+        assert_eq!(ctx.sut.come_up_with_plan().await, "Take over the world!");
+    }
     #[test_context(Context)]
     #[test]
     fn fire_sidekick_if_doesnt_agree_with_conspiracy(ctx: &mut Context) {
@@ -257,20 +399,55 @@ mod tests {
     #[test_context(Context)]
     #[test]
     fn tell_plans_sends_ciphered_message(ctx: &mut Context) {
+        let cipher = Base58Cipher;
+        ctx.sut.shared_key = test_common::SHARED_KEY.to_string();
+        let expected_ciphertext =
+            cipher.transform(test_common::MAIN_SECRET_MESSAGE, test_common::SHARED_KEY);
+
         let mut mock_sidekick = Sidekick::new();
         mock_sidekick
             .expect_tell()
-            .with(eq(String::from(test_common::MAIN_CIPHERED_MESSAGE)))
+            .with(eq(expected_ciphertext.clone()))
             .once()
             .return_const(());
+        mock_sidekick
+            .expect_decipher()
+            .with(
+                eq(expected_ciphertext),
+                mockall::predicate::always(),
+                eq(String::from(test_common::SHARED_KEY)),
+            )
+            .once()
+            .returning(|_, _, _| Ok(String::from(test_common::MAIN_SECRET_MESSAGE)));
         ctx.sut.sidekick = Some(mock_sidekick);
-        let mut mock_cipher = MockCipher::new();
-        mock_cipher
-            .expect_transform()
-            .returning(|secret, _| String::from("+") + secret + "+");
 
-        ctx.sut
-            .tell_plans(test_common::MAIN_SECRET_MESSAGE, &mock_cipher);
+        let plaintext = ctx
+            .sut
+            .tell_plans(test_common::MAIN_SECRET_MESSAGE, &cipher)
+            .expect("sidekick is set")
+            .expect("deciphering should succeed");
+        assert_eq!(plaintext, test_common::MAIN_SECRET_MESSAGE);
+    }
+
+    #[test_context(Context)]
+    #[test]
+    fn tell_plans_round_trips_through_a_real_cipher(ctx: &mut Context) {
+        ctx.sut.shared_key = test_common::SHARED_KEY.to_string();
+        let mut mock_sidekick = Sidekick::new();
+        mock_sidekick.expect_tell().once().return_const(());
+        mock_sidekick
+            .expect_decipher()
+            .once()
+            .returning(|ciphered, cipher, key| cipher.recover(&ciphered, key));
+        ctx.sut.sidekick = Some(mock_sidekick);
+        let cipher = Bech32Cipher;
+
+        let plaintext = ctx
+            .sut
+            .tell_plans(test_common::MAIN_SECRET_MESSAGE, &cipher)
+            .expect("sidekick is set")
+            .expect("deciphering should succeed");
+        assert_eq!(plaintext, test_common::MAIN_SECRET_MESSAGE);
     }
 
     struct Context<'a> {